@@ -0,0 +1,72 @@
+use image::ImageError;
+
+use crate::{convert::Convert, ImageHash, ImageHasher};
+use std::path::Path;
+
+/// aHash: downscales to `width x height`, then sets each bit to whether the
+/// pixel is brighter than the mean pixel value of the downscaled image.
+pub struct AverageHasher {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageHasher for AverageHasher {
+    fn hash_from_path(&self, path: &Path) -> Result<ImageHash, ImageError> {
+        match image::io::Reader::open(path)?.decode() {
+            Ok(img) => Ok(self.hash_from_img(&img)),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn hash_from_img(&self, img: &image::DynamicImage) -> ImageHash {
+        let small = self.convert(img, self.width, self.height);
+        let pixels: Vec<f64> = small.as_bytes().iter().map(|x| *x as f64).collect();
+
+        let mean = pixels.iter().sum::<f64>() / pixels.len() as f64;
+
+        let mut bits = vec![vec![false; self.width as usize]; self.height as usize];
+        for (i, row) in pixels.chunks(self.width as usize).enumerate() {
+            for (j, pixel) in row.iter().enumerate() {
+                bits[i][j] = *pixel > mean;
+            }
+        }
+
+        ImageHash { matrix: bits }
+    }
+}
+
+impl Default for AverageHasher {
+    fn default() -> Self {
+        AverageHasher {
+            width: 8,
+            height: 8,
+        }
+    }
+}
+
+impl Convert for AverageHasher {}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, GrayImage};
+
+    use super::*;
+
+    #[test]
+    fn test_average_hash_from_img() {
+        // Arrange: a 2x2 image already at the hasher's target size, so
+        // `convert` only grayscales it and doesn't resample the pixels.
+        let img = DynamicImage::ImageLuma8(GrayImage::from_raw(2, 2, vec![10, 200, 10, 200]).unwrap());
+
+        let hasher = AverageHasher {
+            width: 2,
+            height: 2,
+        };
+
+        // Act
+        let hash = hasher.hash_from_img(&img);
+
+        // Assert: mean is 105, so only the 200s are above it.
+        assert_eq!(hash.matrix, vec![vec![false, true], vec![false, true]]);
+    }
+}
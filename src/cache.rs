@@ -0,0 +1,204 @@
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use crate::ImageHash;
+
+/// Bumped whenever the hashing algorithm changes in a way that would make
+/// previously cached entries stale.
+const CACHE_VERSION: u32 = 1;
+
+/// On-disk, content-addressed store for `ImageHash` values.
+///
+/// Entries are keyed by the SHA-1 digest of the source image's bytes, so a
+/// cache hit means "this exact file was hashed before", not "a file at this
+/// path was hashed before". Each entry is zlib-compressed and tagged with
+/// the `CACHE_VERSION` it was written with, so bumping the algorithm
+/// invalidates old entries instead of returning stale hashes.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+struct CacheEntry {
+    version: u32,
+    matrix: Vec<Vec<bool>>,
+    dct_matrix: Option<Vec<Vec<f64>>>,
+}
+
+/// A cached hash, plus the intermediate DCT matrix if it was stored.
+pub type CacheHit = (ImageHash, Option<Vec<Vec<f64>>>);
+
+impl Cache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> io::Result<Cache> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Cache { dir })
+    }
+
+    /// Computes the SHA-1 digest of the file at `path`, used as the cache key.
+    pub fn digest(&self, path: &Path) -> io::Result<String> {
+        let bytes = fs::read(path)?;
+        let mut hasher = Sha1::new();
+        hasher.update(&bytes);
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    fn entry_path(&self, digest: &str) -> PathBuf {
+        self.dir.join(format!("{}.zl", digest))
+    }
+
+    pub fn get(&self, digest: &str) -> io::Result<Option<CacheHit>> {
+        let path = self.entry_path(digest);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let compressed = fs::read(path)?;
+        let mut decoder = ZlibDecoder::new(&compressed[..]);
+        let mut raw = Vec::new();
+        decoder.read_to_end(&mut raw)?;
+
+        let entry = decode_entry(&raw)?;
+        if entry.version != CACHE_VERSION {
+            return Ok(None);
+        }
+
+        Ok(Some((ImageHash { matrix: entry.matrix }, entry.dct_matrix)))
+    }
+
+    pub fn put(
+        &self,
+        digest: &str,
+        hash: &ImageHash,
+        dct_matrix: Option<&[Vec<f64>]>,
+    ) -> io::Result<()> {
+        let raw = encode_entry(&CacheEntry {
+            version: CACHE_VERSION,
+            matrix: hash.matrix.clone(),
+            dct_matrix: dct_matrix.map(|m| m.to_vec()),
+        });
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&raw)?;
+        let compressed = encoder.finish()?;
+
+        fs::write(self.entry_path(digest), compressed)
+    }
+}
+
+fn encode_entry(entry: &CacheEntry) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&entry.version.to_le_bytes());
+
+    encode_bit_matrix(&entry.matrix, &mut out);
+
+    match &entry.dct_matrix {
+        Some(matrix) => {
+            out.push(1);
+            encode_f64_matrix(matrix, &mut out);
+        }
+        None => out.push(0),
+    }
+
+    out
+}
+
+fn decode_entry(raw: &[u8]) -> io::Result<CacheEntry> {
+    let mut cursor = raw;
+
+    let version = take_u32(&mut cursor)?;
+    let matrix = decode_bit_matrix(&mut cursor)?;
+
+    let has_dct = take_u8(&mut cursor)?;
+    let dct_matrix = if has_dct == 1 {
+        Some(decode_f64_matrix(&mut cursor)?)
+    } else {
+        None
+    };
+
+    Ok(CacheEntry {
+        version,
+        matrix,
+        dct_matrix,
+    })
+}
+
+fn encode_bit_matrix(matrix: &[Vec<bool>], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(matrix.len() as u32).to_le_bytes());
+    for row in matrix {
+        out.extend_from_slice(&(row.len() as u32).to_le_bytes());
+        out.extend(row.iter().map(|bit| *bit as u8));
+    }
+}
+
+fn decode_bit_matrix(cursor: &mut &[u8]) -> io::Result<Vec<Vec<bool>>> {
+    let rows = take_u32(cursor)? as usize;
+    let mut matrix = Vec::with_capacity(rows);
+    for _ in 0..rows {
+        let cols = take_u32(cursor)? as usize;
+        let mut row = Vec::with_capacity(cols);
+        for _ in 0..cols {
+            row.push(take_u8(cursor)? == 1);
+        }
+        matrix.push(row);
+    }
+    Ok(matrix)
+}
+
+fn encode_f64_matrix(matrix: &[Vec<f64>], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(matrix.len() as u32).to_le_bytes());
+    for row in matrix {
+        out.extend_from_slice(&(row.len() as u32).to_le_bytes());
+        for value in row {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+fn decode_f64_matrix(cursor: &mut &[u8]) -> io::Result<Vec<Vec<f64>>> {
+    let rows = take_u32(cursor)? as usize;
+    let mut matrix = Vec::with_capacity(rows);
+    for _ in 0..rows {
+        let cols = take_u32(cursor)? as usize;
+        let mut row = Vec::with_capacity(cols);
+        for _ in 0..cols {
+            row.push(take_f64(cursor)?);
+        }
+        matrix.push(row);
+    }
+    Ok(matrix)
+}
+
+fn take_u8(cursor: &mut &[u8]) -> io::Result<u8> {
+    if cursor.is_empty() {
+        return Err(unexpected_eof());
+    }
+    let value = cursor[0];
+    *cursor = &cursor[1..];
+    Ok(value)
+}
+
+fn take_u32(cursor: &mut &[u8]) -> io::Result<u32> {
+    if cursor.len() < 4 {
+        return Err(unexpected_eof());
+    }
+    let value = u32::from_le_bytes(cursor[..4].try_into().unwrap());
+    *cursor = &cursor[4..];
+    Ok(value)
+}
+
+fn take_f64(cursor: &mut &[u8]) -> io::Result<f64> {
+    if cursor.len() < 8 {
+        return Err(unexpected_eof());
+    }
+    let value = f64::from_le_bytes(cursor[..8].try_into().unwrap());
+    *cursor = &cursor[8..];
+    Ok(value)
+}
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "truncated cache entry")
+}
@@ -0,0 +1,10 @@
+use image::{imageops::FilterType, DynamicImage};
+
+/// Shared helper for hashers that need to downscale an image to a fixed
+/// grid and flatten it to grayscale before extracting bits from it.
+pub trait Convert {
+    fn convert(&self, img: &DynamicImage, width: u32, height: u32) -> DynamicImage {
+        img.resize_exact(width, height, FilterType::Lanczos3)
+            .grayscale()
+    }
+}
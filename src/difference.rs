@@ -0,0 +1,72 @@
+use image::ImageError;
+
+use crate::{convert::Convert, ImageHash, ImageHasher};
+use std::path::Path;
+
+/// dHash: downscales to `(width + 1) x height`, then sets each bit to
+/// whether a pixel is brighter than its right-hand neighbour.
+pub struct DifferenceHasher {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ImageHasher for DifferenceHasher {
+    fn hash_from_path(&self, path: &Path) -> Result<ImageHash, ImageError> {
+        match image::io::Reader::open(path)?.decode() {
+            Ok(img) => Ok(self.hash_from_img(&img)),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn hash_from_img(&self, img: &image::DynamicImage) -> ImageHash {
+        let small = self.convert(img, self.width + 1, self.height);
+        let row_width = (self.width + 1) as usize;
+        let pixels: Vec<f64> = small.as_bytes().iter().map(|x| *x as f64).collect();
+
+        let mut bits = vec![vec![false; self.width as usize]; self.height as usize];
+        for (i, row) in pixels.chunks(row_width).enumerate() {
+            for j in 0..self.width as usize {
+                bits[i][j] = row[j] > row[j + 1];
+            }
+        }
+
+        ImageHash { matrix: bits }
+    }
+}
+
+impl Default for DifferenceHasher {
+    fn default() -> Self {
+        DifferenceHasher {
+            width: 8,
+            height: 8,
+        }
+    }
+}
+
+impl Convert for DifferenceHasher {}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, GrayImage};
+
+    use super::*;
+
+    #[test]
+    fn test_difference_hash_from_img() {
+        // Arrange: a 3x1 image already at the hasher's target size
+        // (width + 1 x height), so `convert` doesn't resample the pixels.
+        let img = DynamicImage::ImageLuma8(GrayImage::from_raw(3, 1, vec![10, 200, 10]).unwrap());
+
+        let hasher = DifferenceHasher {
+            width: 2,
+            height: 1,
+        };
+
+        // Act
+        let hash = hasher.hash_from_img(&img);
+
+        // Assert: pixel[0] < pixel[1] > pixel[2], so only the second
+        // comparison (pixel[1] > pixel[2]) is true.
+        assert_eq!(hash.matrix, vec![vec![false, true]]);
+    }
+}
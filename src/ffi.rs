@@ -0,0 +1,89 @@
+//! C-compatible entry points for non-Rust callers (Python via `ctypes`, C,
+//! etc). Only compiled in with the `ffi` feature, so the default build
+//! stays pure Rust.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::path::Path;
+
+use crate::{perceptual::PerceptualHasher, ImageHash, ImageHasher};
+
+/// Hashes the image at `path` and returns it as a heap-allocated C string
+/// in the same format as `ImageHash::python_safe_encode`. The caller owns
+/// the returned pointer and must free it with `imghash_free_string`.
+/// Returns a null pointer if `path` isn't valid UTF-8 or the image can't
+/// be decoded.
+///
+/// # Safety
+/// `path` must be a null-terminated, valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn imghash_hash_path(
+    path: *const c_char,
+    width: u32,
+    height: u32,
+    factor: u32,
+) -> *mut c_char {
+    let path = match CStr::from_ptr(path).to_str() {
+        Ok(path) => path,
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    let hasher = PerceptualHasher {
+        width,
+        height,
+        factor,
+        fast_dct: false,
+    };
+
+    match hasher.hash_from_path(Path::new(path)) {
+        Ok(hash) => encode_hash(&hash),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Frees a string previously returned by `imghash_hash_path`.
+///
+/// # Safety
+/// `s` must either be null or a pointer previously returned by
+/// `imghash_hash_path`, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn imghash_free_string(s: *mut c_char) {
+    if s.is_null() {
+        return;
+    }
+    drop(CString::from_raw(s));
+}
+
+/// Computes the Hamming distance between two `python_safe_encode`d hash
+/// strings of equal length. Returns `-1` if either string is invalid or
+/// they differ in length.
+///
+/// # Safety
+/// `a` and `b` must be null-terminated, valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn imghash_distance(a: *const c_char, b: *const c_char) -> i64 {
+    let (a, b) = match (CStr::from_ptr(a).to_str(), CStr::from_ptr(b).to_str()) {
+        (Ok(a), Ok(b)) => (a, b),
+        _ => return -1,
+    };
+
+    if a.len() != b.len() {
+        return -1;
+    }
+
+    a.chars()
+        .zip(b.chars())
+        .try_fold(0i64, |acc, (x, y)| {
+            let x = x.to_digit(16)?;
+            let y = y.to_digit(16)?;
+            Some(acc + (x ^ y).count_ones() as i64)
+        })
+        .unwrap_or(-1)
+}
+
+fn encode_hash(hash: &ImageHash) -> *mut c_char {
+    match CString::new(hash.python_safe_encode()) {
+        Ok(s) => s.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
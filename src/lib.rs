@@ -0,0 +1,235 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{DynamicImage, ImageError};
+use std::path::Path;
+
+pub mod average;
+pub mod cache;
+pub mod convert;
+pub mod difference;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod math;
+pub mod perceptual;
+
+pub use average::AverageHasher;
+pub use difference::DifferenceHasher;
+pub use perceptual::PerceptualHasher;
+
+/// A perceptual image hash: a grid of bits produced by one of the
+/// `ImageHasher` implementations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageHash {
+    pub matrix: Vec<Vec<bool>>,
+}
+
+/// Anything that can turn an image into an `ImageHash`.
+pub trait ImageHasher {
+    fn hash_from_path(&self, path: &Path) -> Result<ImageHash, ImageError>;
+    fn hash_from_img(&self, img: &DynamicImage) -> ImageHash;
+}
+
+/// Error returned when an `ImageHash` operation fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashError {
+    DimensionMismatch {
+        width_a: usize,
+        height_a: usize,
+        width_b: usize,
+        height_b: usize,
+    },
+    Base64Decode(String),
+}
+
+impl std::fmt::Display for HashError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashError::DimensionMismatch {
+                width_a,
+                height_a,
+                width_b,
+                height_b,
+            } => write!(
+                f,
+                "hash dimensions do not match: {}x{} vs {}x{}",
+                width_a, height_a, width_b, height_b
+            ),
+            HashError::Base64Decode(message) => write!(f, "invalid base64 hash: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for HashError {}
+
+impl ImageHash {
+    pub fn python_safe_encode(&self) -> String {
+        self.matrix
+            .iter()
+            .map(|row| row.iter().fold(0u8, |byte, &bit| (byte << 1) | bit as u8))
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Counts the number of differing bits between `self` and `other`.
+    ///
+    /// Returns a [`HashError::DimensionMismatch`] if the two hashes were
+    /// computed at different matrix dimensions, since the Hamming distance
+    /// is only meaningful when comparing like-sized hashes.
+    pub fn distance(&self, other: &ImageHash) -> Result<u32, HashError> {
+        let height_a = self.matrix.len();
+        let height_b = other.matrix.len();
+        let width_a = self.matrix.first().map_or(0, Vec::len);
+        let width_b = other.matrix.first().map_or(0, Vec::len);
+
+        if height_a != height_b || width_a != width_b {
+            return Err(HashError::DimensionMismatch {
+                width_a,
+                height_a,
+                width_b,
+                height_b,
+            });
+        }
+
+        let distance = self
+            .matrix
+            .iter()
+            .zip(other.matrix.iter())
+            .flat_map(|(row_a, row_b)| row_a.iter().zip(row_b.iter()))
+            .filter(|(bit_a, bit_b)| bit_a != bit_b)
+            .count();
+
+        Ok(distance as u32)
+    }
+
+    /// Convenience wrapper around [`ImageHash::distance`] that reports
+    /// whether `self` and `other` are within `threshold` bits of each other.
+    pub fn similar(&self, other: &ImageHash, threshold: u32) -> Result<bool, HashError> {
+        Ok(self.distance(other)? <= threshold)
+    }
+
+    /// Packs the bit matrix (row-major, MSB-first) into bytes and
+    /// base64-encodes them, for compact storage alongside e.g. a database
+    /// row. Reverse with [`ImageHash::from_base64`].
+    pub fn to_base64(&self) -> String {
+        let bits: Vec<bool> = self.matrix.iter().flatten().copied().collect();
+        let bytes: Vec<u8> = bits
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |byte, (i, &bit)| byte | ((bit as u8) << (7 - i)))
+            })
+            .collect();
+
+        STANDARD.encode(bytes)
+    }
+
+    /// Reconstructs an `ImageHash` previously serialized with
+    /// [`ImageHash::to_base64`]. `width` and `height` must match the
+    /// dimensions the hash was originally computed at.
+    pub fn from_base64(s: &str, width: u32, height: u32) -> Result<ImageHash, HashError> {
+        let bytes = STANDARD
+            .decode(s)
+            .map_err(|e| HashError::Base64Decode(e.to_string()))?;
+
+        let total_bits = (width as usize) * (height as usize);
+        let expected_bytes = total_bits.div_ceil(8);
+
+        if bytes.len() != expected_bytes {
+            return Err(HashError::Base64Decode(format!(
+                "expected {} bytes for a {}x{} hash, got {}",
+                expected_bytes,
+                width,
+                height,
+                bytes.len()
+            )));
+        }
+
+        let bits: Vec<bool> = bytes
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> (7 - i)) & 1 == 1))
+            .take(total_bits)
+            .collect();
+
+        let matrix = bits
+            .chunks(width as usize)
+            .map(|row| row.to_vec())
+            .collect();
+
+        Ok(ImageHash { matrix })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_from_bits(bits: &[&[bool]]) -> ImageHash {
+        ImageHash {
+            matrix: bits.iter().map(|row| row.to_vec()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_distance_identical() {
+        let a = hash_from_bits(&[&[true, false], &[false, true]]);
+        let b = a.clone();
+        assert_eq!(a.distance(&b).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_distance_counts_differing_bits() {
+        let a = hash_from_bits(&[&[true, false], &[false, true]]);
+        let b = hash_from_bits(&[&[false, false], &[false, false]]);
+        assert_eq!(a.distance(&b).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_distance_dimension_mismatch() {
+        let a = hash_from_bits(&[&[true, false]]);
+        let b = hash_from_bits(&[&[true, false], &[false, true]]);
+        assert!(matches!(
+            a.distance(&b),
+            Err(HashError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_similar_respects_threshold() {
+        let a = hash_from_bits(&[&[true, false], &[false, true]]);
+        let b = hash_from_bits(&[&[false, false], &[false, false]]);
+        assert!(!a.similar(&b, 1).unwrap());
+        assert!(a.similar(&b, 2).unwrap());
+    }
+
+    #[test]
+    fn test_base64_round_trip() {
+        let hash = hash_from_bits(&[&[true, false, true], &[false, true, false]]);
+        let encoded = hash.to_base64();
+        let decoded = ImageHash::from_base64(&encoded, 3, 2).unwrap();
+        assert_eq!(hash, decoded);
+    }
+
+    #[test]
+    fn test_from_base64_rejects_wrong_dimensions() {
+        let hash = hash_from_bits(&[&[true, false, true], &[false, true, false]]);
+        let encoded = hash.to_base64();
+        assert!(matches!(
+            ImageHash::from_base64(&encoded, 4, 4),
+            Err(HashError::Base64Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_base64_rejects_trailing_bytes() {
+        let hash = hash_from_bits(&[&[true, false, true], &[false, true, false]]);
+        let mut encoded_bytes = STANDARD.decode(hash.to_base64()).unwrap();
+        encoded_bytes.push(0xff);
+        let over_long = STANDARD.encode(encoded_bytes);
+
+        assert!(matches!(
+            ImageHash::from_base64(&over_long, 3, 2),
+            Err(HashError::Base64Decode(_))
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use rustfft::{num_complex::Complex64, Fft, FftPlanner};
+
+/// Which direction of a 2D matrix a transform should be applied along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Row,
+    Column,
+}
+
+/// Applies a naive O(n^2) DCT-II to every row (or column) of `matrix`.
+pub fn dct2_over_matrix(matrix: &[Vec<f64>], axis: Axis) -> Vec<Vec<f64>> {
+    match axis {
+        Axis::Row => matrix.iter().map(|row| dct2(row)).collect(),
+        Axis::Column => transpose(&dct2_over_matrix(&transpose(matrix), Axis::Row)),
+    }
+}
+
+/// Applies the same DCT-II as `dct2_over_matrix`, but in O(n log n) per row
+/// by going through a real FFT of the even-symmetry extension instead of
+/// the dense O(n^2) sum. The coefficients have the same scale as
+/// `dct2_over_matrix`'s and match them up to floating-point rounding.
+pub fn dct2_over_matrix_fast(matrix: &[Vec<f64>], axis: Axis) -> Vec<Vec<f64>> {
+    match axis {
+        Axis::Row => {
+            let Some(n) = matrix.first().map(Vec::len) else {
+                return Vec::new();
+            };
+
+            let mut planner = FftPlanner::new();
+            let fft = planner.plan_fft_forward(2 * n);
+
+            matrix.iter().map(|row| dct2_fast(row, &fft)).collect()
+        }
+        Axis::Column => transpose(&dct2_over_matrix_fast(&transpose(matrix), Axis::Row)),
+    }
+}
+
+fn dct2(input: &[f64]) -> Vec<f64> {
+    let n = input.len();
+    (0..n)
+        .map(|k| {
+            let sum: f64 = input
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    x * (std::f64::consts::PI / n as f64 * (i as f64 + 0.5) * k as f64).cos()
+                })
+                .sum();
+            2.0 * sum
+        })
+        .collect()
+}
+
+/// DCT-II, scaled as in `dct2`, via an even-symmetry extension
+/// `[x, reverse(x)]` of length `2n`: its FFT's bin `k`, rotated by
+/// `exp(-i*pi*k/2n)`, has the DCT-II coefficient `k` of `x` as its real
+/// part. `fft` must be a forward FFT plan of length `2 * input.len()`.
+fn dct2_fast(input: &[f64], fft: &Arc<dyn Fft<f64>>) -> Vec<f64> {
+    let n = input.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut extended: Vec<Complex64> = input
+        .iter()
+        .chain(input.iter().rev())
+        .map(|x| Complex64::new(*x, 0.0))
+        .collect();
+
+    fft.process(&mut extended);
+
+    (0..n)
+        .map(|k| {
+            let angle = -std::f64::consts::PI * k as f64 / (2.0 * n as f64);
+            let rotation = Complex64::new(angle.cos(), angle.sin());
+            (extended[k] * rotation).re
+        })
+        .collect()
+}
+
+fn transpose(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    if matrix.is_empty() {
+        return Vec::new();
+    }
+    let cols = matrix[0].len();
+    (0..cols)
+        .map(|c| matrix.iter().map(|row| row[c]).collect())
+        .collect()
+}
+
+/// Returns the median of `values`, or `None` if it is empty.
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_odd() {
+        assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+    }
+
+    #[test]
+    fn test_median_even() {
+        assert_eq!(median(&[1.0, 2.0, 3.0, 4.0]), Some(2.5));
+    }
+
+    #[test]
+    fn test_median_empty() {
+        assert_eq!(median(&[]), None);
+    }
+}
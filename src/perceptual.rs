@@ -1,8 +1,9 @@
 use image::ImageError;
 
 use crate::{
+    cache::Cache,
     convert::Convert,
-    math::{dct2_over_matrix, median, Axis},
+    math::{dct2_over_matrix, dct2_over_matrix_fast, median, Axis},
     ImageHash, ImageHasher,
 };
 use std::path::Path;
@@ -11,6 +12,10 @@ pub struct PerceptualHasher {
     pub width: u32,
     pub height: u32,
     pub factor: u32,
+    /// When set, compute the DCT via the O(n log n) FFT-based path instead
+    /// of the naive O(n^2) one. Both paths compute the same coefficients,
+    /// up to floating-point rounding.
+    pub fast_dct: bool,
 }
 
 impl ImageHasher for PerceptualHasher {
@@ -22,6 +27,15 @@ impl ImageHasher for PerceptualHasher {
     }
 
     fn hash_from_img(&self, img: &image::DynamicImage) -> ImageHash {
+        self.compute(img).0
+    }
+}
+
+impl PerceptualHasher {
+    /// Runs the `Convert` + DCT + median pipeline, returning both the
+    /// resulting hash and the intermediate (rescaled) DCT matrix so callers
+    /// that cache hashes can cache the matrix alongside them.
+    fn compute(&self, img: &image::DynamicImage) -> (ImageHash, Vec<Vec<f64>>) {
         let high_freq = self.convert(img, self.width * self.factor, self.height * self.factor);
 
         // convert the higher frequency image to a matrix
@@ -32,10 +46,17 @@ impl ImageHasher for PerceptualHasher {
             .collect();
 
         // now we compute the DCT for each column and then for each row
-        let dct_matrix = dct2_over_matrix(
-            &dct2_over_matrix(&high_freq_matrix, Axis::Column),
-            Axis::Row,
-        );
+        let dct_matrix = if self.fast_dct {
+            dct2_over_matrix_fast(
+                &dct2_over_matrix_fast(&high_freq_matrix, Axis::Column),
+                Axis::Row,
+            )
+        } else {
+            dct2_over_matrix(
+                &dct2_over_matrix(&high_freq_matrix, Axis::Column),
+                Axis::Row,
+            )
+        };
 
         // now we rescale the dct matrix to the actual given width and height
         let scaled_matrix: Vec<Vec<f64>> = dct_matrix
@@ -56,7 +77,30 @@ impl ImageHasher for PerceptualHasher {
             }
         }
 
-        ImageHash { matrix: bits }
+        (ImageHash { matrix: bits }, scaled_matrix)
+    }
+
+    /// Same as `hash_from_path`, but looks the image up in `cache` by
+    /// content digest first, and only runs the `Convert` + DCT + median
+    /// pipeline on a miss. On a miss, the intermediate DCT matrix is cached
+    /// alongside the hash so it doesn't need to be recomputed either.
+    pub fn hash_from_path_cached(
+        &self,
+        path: &Path,
+        cache: &Cache,
+    ) -> Result<ImageHash, ImageError> {
+        let digest = cache.digest(path).map_err(ImageError::IoError)?;
+
+        if let Some((hash, _dct_matrix)) = cache.get(&digest).map_err(ImageError::IoError)? {
+            return Ok(hash);
+        }
+
+        let img = image::io::Reader::open(path)?.decode()?;
+        let (hash, dct_matrix) = self.compute(&img);
+        cache
+            .put(&digest, &hash, Some(&dct_matrix))
+            .map_err(ImageError::IoError)?;
+        Ok(hash)
     }
 }
 
@@ -66,6 +110,7 @@ impl Default for PerceptualHasher {
             width: 8,
             height: 8,
             factor: 4,
+            fast_dct: false,
         }
     }
 }
@@ -99,6 +144,26 @@ mod tests {
         assert_eq!(hash.python_safe_encode(), "157d1d1b193c7c1c")
     }
 
+    #[test]
+    fn test_perceptual_hash_from_img_fast_dct() {
+        // Arrange
+        let img = ImageReader::open(Path::new(TEST_IMG))
+            .unwrap()
+            .decode()
+            .unwrap();
+
+        let hasher = PerceptualHasher {
+            fast_dct: true,
+            ..Default::default()
+        };
+
+        // Act
+        let hash = hasher.hash_from_img(&img);
+
+        // Assert
+        assert_eq!(hash.python_safe_encode(), "157d1d1b193c7c1c")
+    }
+
     #[test]
     fn test_perceptual_hash_from_path() {
         // Arrange